@@ -1,44 +1,358 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use sha2::{Sha256, Digest};
+use num_bigint::BigUint;
+use serde::{Serialize, Deserialize};
+
+// How often (in blocks) we re-evaluate the difficulty.
+const RETARGET_INTERVAL: u64 = 10;
+// How long each block should take if difficulty is well-tuned; a retarget
+// window should take RETARGET_INTERVAL times this.
+const TARGET_BLOCK_INTERVAL_SECS: u64 = 10;
+// Leading zero bits required of a block hash when the chain starts.
+const INITIAL_DIFFICULTY: u32 = 16;
+// Difficulty can never retarget above this many leading zero bits, which
+// keeps target_for_difficulty's `256 - difficulty` from underflowing (it
+// would above 256).
+const MAX_DIFFICULTY: u32 = 255;
+// Give up on a block once this many nonces have been tried.
+const MAX_NONCE: u64 = 1_000_000;
+// How many pending transactions a single block bundles at most.
+const MAX_TRANSACTIONS_PER_BLOCK: usize = 10;
+
+// Errors reading from or writing to the on-disk store. Variants carry the
+// underlying error purely for `{:?}` diagnostics, so their payload never
+// gets read directly.
+#[derive(Debug)]
+#[allow(dead_code)]
+enum StorageError {
+    Db(sled::Error),
+    Serde(serde_json::Error),
+}
+
+impl From<sled::Error> for StorageError {
+    fn from(err: sled::Error) -> Self {
+        StorageError::Db(err)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(err: serde_json::Error) -> Self {
+        StorageError::Serde(err)
+    }
+}
+
+// Errors that can surface while searching for a valid proof-of-work.
+#[derive(Debug)]
+enum MiningError {
+    // The nonce space was exhausted without finding a hash below target.
+    Iteration,
+    // A block was found but couldn't be persisted; payload is Debug-only.
+    #[allow(dead_code)]
+    Storage(StorageError),
+}
+
+impl From<StorageError> for MiningError {
+    fn from(err: StorageError) -> Self {
+        MiningError::Storage(err)
+    }
+}
+
+// Errors describing why a chain failed to validate, identified by the
+// index of the first offending block. The index is read by tests
+// (asserting on which block failed) but only appears via `{:?}` in the
+// non-test binary, where it'd otherwise look unread.
+#[derive(Debug)]
+#[cfg_attr(not(test), allow(dead_code))]
+enum ValidationError {
+    IndexGap(u64),
+    BrokenLink(u64),
+    HashMismatch(u64),
+    MerkleMismatch(u64),
+    InsufficientDifficulty(u64),
+}
+
+// A single entry bundled into a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Transaction {
+    payload: String,
+}
 
 // Define a Block structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Block {
     index: u64,
     timestamp: u64,
-    data: String,
+    transactions: Vec<Transaction>,
+    merkle_root: String,
     prev_hash: String,
     hash: String,
     nonce: u64,
+    // Leading zero bits the hash was required to satisfy when mined.
+    difficulty: u32,
 }
 
-// Blockchain structure with a mutex for thread-safe access
+// Blockchain structure with a mutex for thread-safe access. `chain` is an
+// in-memory cache of the active (best) branch in front of `store`, the
+// durable source of truth for every block ever seen, forks included.
 struct Blockchain {
     chain: Mutex<Vec<Block>>,
+    // Position of each active-chain block's hash within `chain`.
+    hash_index: Mutex<HashMap<String, usize>>,
+    // Every known block, keyed by hash, including blocks on side branches.
+    blocks_by_hash: Mutex<HashMap<String, Block>>,
+    difficulty: Mutex<u32>,
+    // Transactions submitted by producers, waiting to be mined into a block.
+    pending: Mutex<VecDeque<Transaction>>,
+    store: sled::Db,
 }
 
 impl Blockchain {
-    fn new() -> Self {
-        let genesis_block = Block {
-            index: 0,
-            timestamp: now(),
-            data: "Genesis Block".to_string(),
-            prev_hash: "0".repeat(64),
-            hash: "0".repeat(64),
-            nonce: 0,
+    // Open (or create) the on-disk store at `path`, reload every block it
+    // holds and adopt the best branch among them, creating the genesis block
+    // (fixed all-zero hash, not mined) on first run.
+    fn open<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
+        let store = sled::open(path)?;
+
+        let mut blocks_by_hash = HashMap::new();
+        for entry in store.iter() {
+            let (_, value) = entry?;
+            let block: Block = serde_json::from_slice(&value)?;
+            blocks_by_hash.insert(block.hash.clone(), block);
+        }
+
+        if blocks_by_hash.is_empty() {
+            let genesis_transactions = vec![Transaction { payload: "Genesis Block".to_string() }];
+            let genesis_block = Block {
+                index: 0,
+                timestamp: now(),
+                merkle_root: merkle_root(&genesis_transactions),
+                transactions: genesis_transactions,
+                prev_hash: "0".repeat(64),
+                hash: "0".repeat(64),
+                nonce: 0,
+                difficulty: INITIAL_DIFFICULTY,
+            };
+            Self::persist_block(&store, &genesis_block)?;
+            blocks_by_hash.insert(genesis_block.hash.clone(), genesis_block);
+        }
+
+        let blockchain = Blockchain {
+            chain: Mutex::new(Vec::new()),
+            hash_index: Mutex::new(HashMap::new()),
+            blocks_by_hash: Mutex::new(blocks_by_hash),
+            difficulty: Mutex::new(INITIAL_DIFFICULTY),
+            pending: Mutex::new(VecDeque::new()),
+            store,
         };
+        blockchain.adopt_chain(blockchain.best_chain());
+
+        Ok(blockchain)
+    }
+
+    // Write a block through to the store in a single batched insert, keyed
+    // by its hash so competing blocks at the same index never collide.
+    fn persist_block(store: &sled::Db, block: &Block) -> Result<(), StorageError> {
+        let mut batch = sled::Batch::default();
+        batch.insert(block.hash.as_bytes(), serde_json::to_vec(block)?);
+        store.apply_batch(batch)?;
+        Ok(())
+    }
+
+    // Look up any known block (active chain or side branch) by hash. Active
+    // chain hits go through `hash_index` to the block's position in
+    // `chain`, an O(1) path that doesn't need to touch `blocks_by_hash`;
+    // side-branch blocks fall back to the full map.
+    //
+    // Not yet called outside of tests, but it's the lookup fork-choice and
+    // reorg handling will need, so it stays part of the public surface.
+    #[allow(dead_code)]
+    fn get_block(&self, hash: &str) -> Option<Block> {
+        if let Some(&position) = self.hash_index.lock().unwrap().get(hash) {
+            return self.chain.lock().unwrap().get(position).cloned();
+        }
+        self.blocks_by_hash.lock().unwrap().get(hash).cloned()
+    }
+
+    // Follow `prev_hash` to the block's parent, if it's known.
+    #[allow(dead_code)]
+    fn parent_of(&self, block: &Block) -> Option<Block> {
+        self.get_block(&block.prev_hash)
+    }
+
+    // The longest (highest cumulative-difficulty) branch among all known
+    // blocks, oldest block first.
+    fn best_chain(&self) -> Vec<Block> {
+        let blocks_by_hash = self.blocks_by_hash.lock().unwrap();
+        // Genesis's own `prev_hash` is a placeholder sentinel, not a real
+        // parent link, and happens to equal its own hash — skip it so
+        // genesis isn't mistaken for "referenced" (i.e. not a tip) when
+        // it's the only block.
+        let referenced: HashSet<&str> = blocks_by_hash
+            .values()
+            .filter(|b| b.index > 0)
+            .map(|b| b.prev_hash.as_str())
+            .collect();
+        let tips: Vec<Block> = blocks_by_hash
+            .values()
+            .filter(|b| !referenced.contains(b.hash.as_str()))
+            .cloned()
+            .collect();
+
+        // Fold instead of `max_by_key`: iterating a `HashMap`-derived `Vec`
+        // gives no stable order, so ties need an explicit, deterministic
+        // tiebreak (lowest tip hash) or the same stored data could adopt
+        // a different branch on every run.
+        tips.into_iter()
+            .map(|tip| Self::branch_ending_at(&blocks_by_hash, tip))
+            .reduce(|best, candidate| {
+                let best_work = Self::cumulative_work(&best);
+                let candidate_work = Self::cumulative_work(&candidate);
+                match candidate_work.cmp(&best_work) {
+                    std::cmp::Ordering::Greater => candidate,
+                    std::cmp::Ordering::Less => best,
+                    std::cmp::Ordering::Equal => {
+                        if candidate.last().unwrap().hash < best.last().unwrap().hash {
+                            candidate
+                        } else {
+                            best
+                        }
+                    }
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    // Walk a tip's ancestors back to genesis, oldest block first.
+    fn branch_ending_at(blocks_by_hash: &HashMap<String, Block>, tip: Block) -> Vec<Block> {
+        let mut branch = vec![tip];
+        while branch.last().unwrap().index > 0 {
+            match blocks_by_hash.get(&branch.last().unwrap().prev_hash) {
+                Some(parent) => branch.push(parent.clone()),
+                None => break,
+            }
+        }
+        branch.reverse();
+        branch
+    }
+
+    // A rough proof-of-work measure: difficulty `d` is worth `2^d` units
+    // (capped at `2^127` to fit a `u128`), summed over the branch, the same
+    // way real chains compare forks.
+    fn cumulative_work(branch: &[Block]) -> u128 {
+        branch.iter().map(|b| 1u128 << b.difficulty.min(127)).sum()
+    }
+
+    // Make `chain` the active branch and rebuild the hash index and
+    // difficulty to match its tip.
+    fn adopt_chain(&self, chain: Vec<Block>) {
+        let mut hash_index = HashMap::new();
+        for (i, block) in chain.iter().enumerate() {
+            hash_index.insert(block.hash.clone(), i);
+        }
+        let difficulty = chain.last().map(|b| b.difficulty).unwrap_or(INITIAL_DIFFICULTY);
+
+        *self.chain.lock().unwrap() = chain;
+        *self.hash_index.lock().unwrap() = hash_index;
+        *self.difficulty.lock().unwrap() = difficulty;
+    }
+
+    // Record a block observed from outside the local miner (e.g. a rival
+    // branch). If it extends the active chain's tip it's appended
+    // directly; if it instead forks off an earlier block it's tracked as
+    // a side branch, and the active chain reorgs onto it if its
+    // cumulative difficulty now wins.
+    //
+    // Not yet called outside of tests; see `get_block`.
+    #[allow(dead_code)]
+    fn insert_block(&self, block: Block) -> Result<(), StorageError> {
+        Self::persist_block(&self.store, &block)?;
+        self.blocks_by_hash.lock().unwrap().insert(block.hash.clone(), block.clone());
+
+        let extends_tip = self
+            .chain
+            .lock()
+            .unwrap()
+            .last()
+            .map(|tip| tip.hash == block.prev_hash)
+            .unwrap_or(false);
+
+        if extends_tip {
+            let mut chain = self.chain.lock().unwrap();
+            self.hash_index.lock().unwrap().insert(block.hash.clone(), chain.len());
+            chain.push(block);
+        } else {
+            let best = self.best_chain();
+            let current_work = Self::cumulative_work(&self.chain.lock().unwrap());
+            if Self::cumulative_work(&best) > current_work {
+                self.adopt_chain(best);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Producers call this; it never touches the chain, so any number of
+    // threads can submit concurrently without racing on `prev_hash`.
+    fn submit(&self, payload: String) {
+        self.pending.lock().unwrap().push_back(Transaction { payload });
+    }
 
-        Blockchain {
-            chain: Mutex::new(vec![genesis_block]),
+    // Drain up to `MAX_TRANSACTIONS_PER_BLOCK` pending transactions into a
+    // newly mined block. Holds the chain lock across the read-mine-append
+    // so the new block's `prev_hash` is guaranteed to match the true
+    // current tip, even if `mine_pending` is called from several threads
+    // sharing the same `Arc<Blockchain>`. Returns `Ok(false)` once the
+    // pending queue is empty.
+    fn mine_pending(&self) -> Result<bool, MiningError> {
+        let transactions: Vec<Transaction> = {
+            let mut pending = self.pending.lock().unwrap();
+            let batch_size = pending.len().min(MAX_TRANSACTIONS_PER_BLOCK);
+            pending.drain(..batch_size).collect()
+        };
+        if transactions.is_empty() {
+            return Ok(false);
         }
+
+        let mut chain = self.chain.lock().unwrap();
+        let last_block = chain.last().unwrap().clone();
+        let difficulty = *self.difficulty.lock().unwrap();
+        let new_block = mine_block(last_block, transactions, difficulty)?;
+
+        Self::persist_block(&self.store, &new_block)?;
+        self.blocks_by_hash.lock().unwrap().insert(new_block.hash.clone(), new_block.clone());
+        self.hash_index.lock().unwrap().insert(new_block.hash.clone(), chain.len());
+        chain.push(new_block);
+        drop(chain);
+
+        self.retarget();
+        Ok(true)
     }
 
-    fn add_block(&self, data: String) {
-        let last_block = self.chain.lock().unwrap().last().unwrap().clone();
-        let new_block = mine_block(last_block, data);
-        self.chain.lock().unwrap().push(new_block);
+    // Compare the time the last `RETARGET_INTERVAL` blocks actually took
+    // against the target window and nudge the difficulty towards it.
+    fn retarget(&self) {
+        let chain = self.chain.lock().unwrap();
+        let len = chain.len() as u64;
+        if len <= RETARGET_INTERVAL || !(len - 1).is_multiple_of(RETARGET_INTERVAL) {
+            return;
+        }
+
+        let window_start = chain[(len - 1 - RETARGET_INTERVAL) as usize].timestamp;
+        let window_end = chain[(len - 1) as usize].timestamp;
+        let actual_secs = window_end.saturating_sub(window_start);
+        drop(chain);
+
+        let expected_secs = RETARGET_INTERVAL * TARGET_BLOCK_INTERVAL_SECS;
+        let mut difficulty = self.difficulty.lock().unwrap();
+        if actual_secs < expected_secs && *difficulty < MAX_DIFFICULTY {
+            *difficulty += 1;
+        } else if actual_secs > expected_secs && *difficulty > 1 {
+            *difficulty -= 1;
+        }
     }
 
     fn print_chain(&self) {
@@ -47,6 +361,42 @@ impl Blockchain {
             println!("{:#?}", block);
         }
     }
+
+    // Walk the chain and confirm every non-genesis block links correctly,
+    // reproduces its stored hash, and actually meets the difficulty it
+    // claims. Returns the first offending block's index on failure.
+    fn validate_chain(&self) -> Result<(), ValidationError> {
+        let chain = self.chain.lock().unwrap();
+
+        for i in 1..chain.len() {
+            let block = &chain[i];
+            let prev = &chain[i - 1];
+
+            if block.index != prev.index + 1 {
+                return Err(ValidationError::IndexGap(block.index));
+            }
+            if block.prev_hash != prev.hash {
+                return Err(ValidationError::BrokenLink(block.index));
+            }
+
+            let recomputed_root = merkle_root(&block.transactions);
+            if recomputed_root != block.merkle_root {
+                return Err(ValidationError::MerkleMismatch(block.index));
+            }
+
+            let recomputed = hash_block(block.index, block.timestamp, &block.merkle_root, &block.prev_hash, block.nonce);
+            if recomputed != block.hash {
+                return Err(ValidationError::HashMismatch(block.index));
+            }
+
+            let hash_value = BigUint::parse_bytes(block.hash.as_bytes(), 16).unwrap();
+            if hash_value >= target_for_difficulty(block.difficulty) {
+                return Err(ValidationError::InsufficientDifficulty(block.index));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 // Get current UNIX timestamp
@@ -55,38 +405,83 @@ fn now() -> u64 {
 }
 
 // Hash a block using SHA-256
-fn hash_block(index: u64, timestamp: u64, data: &str, prev_hash: &str, nonce: u64) -> String {
-    let input = format!("{}{}{}{}{}", index, timestamp, data, prev_hash, nonce);
+fn hash_block(index: u64, timestamp: u64, merkle_root: &str, prev_hash: &str, nonce: u64) -> String {
+    let input = format!("{}{}{}{}{}", index, timestamp, merkle_root, prev_hash, nonce);
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
     format!("{:x}", hasher.finalize())
 }
 
-// Mining proof-of-work
-fn mine_block(prev_block: Block, data: String) -> Block {
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+// Commit a batch of transactions to a single fixed-size hash: hash each
+// transaction, then repeatedly hash adjacent pairs (duplicating the last
+// leaf at odd-sized levels) until one root remains.
+fn merkle_root(transactions: &[Transaction]) -> String {
+    if transactions.is_empty() {
+        return "0".repeat(64);
+    }
+
+    let mut layer: Vec<String> = transactions
+        .iter()
+        .map(|tx| sha256_hex(tx.payload.as_bytes()))
+        .collect();
+
+    while layer.len() > 1 {
+        if layer.len() % 2 == 1 {
+            layer.push(layer.last().unwrap().clone());
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| sha256_hex(format!("{}{}", pair[0], pair[1]).as_bytes()))
+            .collect();
+    }
+
+    layer.remove(0)
+}
+
+// The 256-bit value a hash must fall below to satisfy `difficulty` leading
+// zero bits.
+fn target_for_difficulty(difficulty: u32) -> BigUint {
+    BigUint::from(1u8) << (256 - difficulty as usize)
+}
+
+// Mining proof-of-work: search for a nonce whose hash, read as a big-endian
+// 256-bit integer, is below the target implied by `difficulty`. Gives up
+// after `MAX_NONCE` tries so callers can retry or report failure.
+fn mine_block(prev_block: Block, transactions: Vec<Transaction>, difficulty: u32) -> Result<Block, MiningError> {
     let index = prev_block.index + 1;
     let timestamp = now();
     let prev_hash = prev_block.hash.clone();
-    let mut nonce = 0;
+    let merkle_root = merkle_root(&transactions);
+    let target = target_for_difficulty(difficulty);
 
-    loop {
-        let hash = hash_block(index, timestamp, &data, &prev_hash, nonce);
-        if &hash[..4] == "0000" {
-            return Block {
+    for nonce in 0..MAX_NONCE {
+        let hash = hash_block(index, timestamp, &merkle_root, &prev_hash, nonce);
+        let hash_value = BigUint::parse_bytes(hash.as_bytes(), 16).unwrap();
+        if hash_value < target {
+            return Ok(Block {
                 index,
                 timestamp,
-                data,
+                transactions,
+                merkle_root,
                 prev_hash,
                 hash,
                 nonce,
-            };
+                difficulty,
+            });
         }
-        nonce += 1;
     }
+
+    Err(MiningError::Iteration)
 }
 
 fn main() {
-    let blockchain = Arc::new(Blockchain::new());
+    let blockchain = Arc::new(Blockchain::open("chain_db").expect("failed to open chain store"));
 
     let mut handles = vec![];
 
@@ -94,7 +489,7 @@ fn main() {
         let blockchain_clone = Arc::clone(&blockchain);
         let handle = thread::spawn(move || {
             let data = format!("Block {}", i + 1);
-            blockchain_clone.add_block(data);
+            blockchain_clone.submit(data);
         });
 
         handles.push(handle);
@@ -104,5 +499,242 @@ fn main() {
         handle.join().unwrap();
     }
 
+    // A single miner drains whatever the producers submitted, one block
+    // at a time, so every block links correctly to the true tip.
+    loop {
+        match blockchain.mine_pending() {
+            Ok(true) => continue,
+            Ok(false) => break,
+            Err(err) => {
+                eprintln!("failed to mine block: {:?}", err);
+                break;
+            }
+        }
+    }
+
     blockchain.print_chain();
+
+    match blockchain.validate_chain() {
+        Ok(()) => println!("chain is valid"),
+        Err(err) => println!("chain is invalid: {:?}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fresh, temp-backed chain so tests never touch the real `chain_db`.
+    fn open_temp_chain() -> (Blockchain, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let blockchain = Blockchain::open(dir.path()).expect("failed to open temp chain store");
+        (blockchain, dir)
+    }
+
+    #[test]
+    fn get_block_and_parent_of_resolve_genesis() {
+        let (blockchain, _dir) = open_temp_chain();
+
+        let genesis_hash = "0".repeat(64);
+        let genesis = blockchain.get_block(&genesis_hash).expect("genesis must be recorded");
+
+        assert_eq!(genesis.index, 0);
+        // Genesis's `prev_hash` is the same sentinel as its own hash, so it
+        // resolves back to itself rather than to `None`.
+        assert_eq!(blockchain.parent_of(&genesis).unwrap().hash, genesis_hash);
+    }
+
+    #[test]
+    fn best_chain_prefers_the_heavier_branch_over_a_lighter_fork() {
+        let (blockchain, _dir) = open_temp_chain();
+
+        let genesis_hash = "0".repeat(64);
+        let genesis = blockchain.get_block(&genesis_hash).unwrap();
+
+        let heavy_block = mine_block(genesis.clone(), vec![Transaction { payload: "heavy".to_string() }], 8).unwrap();
+        blockchain.insert_block(heavy_block.clone()).unwrap();
+
+        // A rival block mined directly on genesis at much lower difficulty:
+        // a side branch that should lose the fork-choice comparison.
+        let light_fork = mine_block(genesis, vec![Transaction { payload: "fork".to_string() }], 1).unwrap();
+        blockchain.insert_block(light_fork.clone()).unwrap();
+
+        let parent = blockchain.parent_of(&light_fork).expect("fork block's parent must resolve");
+        assert_eq!(parent.hash, genesis_hash);
+
+        let best = blockchain.best_chain();
+        assert_eq!(best.last().unwrap().hash, heavy_block.hash, "the heavier branch should win");
+    }
+
+    #[test]
+    fn best_chain_breaks_ties_on_lowest_tip_hash() {
+        let (blockchain, _dir) = open_temp_chain();
+
+        let genesis_hash = "0".repeat(64);
+        let genesis = blockchain.get_block(&genesis_hash).unwrap();
+
+        let branch_a = mine_block(genesis.clone(), vec![Transaction { payload: "a".to_string() }], 4).unwrap();
+        let branch_b = mine_block(genesis, vec![Transaction { payload: "b".to_string() }], 4).unwrap();
+        blockchain.insert_block(branch_a.clone()).unwrap();
+        blockchain.insert_block(branch_b.clone()).unwrap();
+
+        let expected = std::cmp::min(&branch_a.hash, &branch_b.hash);
+        assert_eq!(&blockchain.best_chain().last().unwrap().hash, expected);
+    }
+
+    #[test]
+    fn target_for_difficulty_tightens_as_difficulty_rises() {
+        assert!(target_for_difficulty(10) > target_for_difficulty(11));
+    }
+
+    #[test]
+    fn target_for_difficulty_never_underflows_at_the_ceiling() {
+        // MAX_DIFFICULTY is chosen so `256 - difficulty` can't underflow.
+        let _ = target_for_difficulty(MAX_DIFFICULTY);
+    }
+
+    // Push `RETARGET_INTERVAL` synthetic blocks directly onto `chain`,
+    // spaced `secs_per_block` apart, so `retarget()` has a full window to
+    // evaluate without actually mining anything.
+    fn push_retarget_window(blockchain: &Blockchain, secs_per_block: u64) {
+        let mut chain = blockchain.chain.lock().unwrap();
+        let genesis = chain[0].clone();
+        for i in 1..=RETARGET_INTERVAL {
+            chain.push(Block {
+                index: i,
+                timestamp: genesis.timestamp + i * secs_per_block,
+                ..genesis.clone()
+            });
+        }
+    }
+
+    #[test]
+    fn retarget_raises_difficulty_when_blocks_arrive_too_fast() {
+        let (blockchain, _dir) = open_temp_chain();
+        push_retarget_window(&blockchain, 0);
+
+        let before = *blockchain.difficulty.lock().unwrap();
+        blockchain.retarget();
+        assert!(*blockchain.difficulty.lock().unwrap() > before);
+    }
+
+    #[test]
+    fn retarget_lowers_difficulty_when_blocks_arrive_too_slowly() {
+        let (blockchain, _dir) = open_temp_chain();
+        push_retarget_window(&blockchain, TARGET_BLOCK_INTERVAL_SECS * 10);
+
+        let before = *blockchain.difficulty.lock().unwrap();
+        blockchain.retarget();
+        assert!(*blockchain.difficulty.lock().unwrap() < before);
+    }
+
+    // Mine a block that legitimately extends `blockchain`'s tip and append
+    // it directly to `chain`, bypassing the mempool.
+    fn append_valid_block(blockchain: &Blockchain, payload: &str) -> Block {
+        let prev = blockchain.chain.lock().unwrap().last().unwrap().clone();
+        let difficulty = *blockchain.difficulty.lock().unwrap();
+        let block = mine_block(prev, vec![Transaction { payload: payload.to_string() }], difficulty).unwrap();
+        blockchain.chain.lock().unwrap().push(block.clone());
+        block
+    }
+
+    fn corrupt_last_block(blockchain: &Blockchain, f: impl FnOnce(&mut Block)) {
+        let mut chain = blockchain.chain.lock().unwrap();
+        f(chain.last_mut().unwrap());
+    }
+
+    #[test]
+    fn validate_chain_accepts_a_well_formed_chain() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        append_valid_block(&blockchain, "b");
+        assert!(blockchain.validate_chain().is_ok());
+    }
+
+    #[test]
+    fn validate_chain_detects_an_index_gap() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        corrupt_last_block(&blockchain, |b| b.index += 1);
+
+        let err = blockchain.validate_chain().unwrap_err();
+        assert!(matches!(err, ValidationError::IndexGap(2)));
+    }
+
+    #[test]
+    fn validate_chain_detects_a_broken_link() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        corrupt_last_block(&blockchain, |b| b.prev_hash = "f".repeat(64));
+
+        let err = blockchain.validate_chain().unwrap_err();
+        assert!(matches!(err, ValidationError::BrokenLink(1)));
+    }
+
+    #[test]
+    fn validate_chain_detects_a_merkle_mismatch() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        corrupt_last_block(&blockchain, |b| {
+            b.transactions.push(Transaction { payload: "extra".to_string() })
+        });
+
+        let err = blockchain.validate_chain().unwrap_err();
+        assert!(matches!(err, ValidationError::MerkleMismatch(1)));
+    }
+
+    #[test]
+    fn validate_chain_detects_a_hash_mismatch() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        corrupt_last_block(&blockchain, |b| b.hash = "f".repeat(64));
+
+        let err = blockchain.validate_chain().unwrap_err();
+        assert!(matches!(err, ValidationError::HashMismatch(1)));
+    }
+
+    #[test]
+    fn validate_chain_detects_insufficient_difficulty() {
+        let (blockchain, _dir) = open_temp_chain();
+        append_valid_block(&blockchain, "a");
+        corrupt_last_block(&blockchain, |b| b.difficulty = MAX_DIFFICULTY);
+
+        let err = blockchain.validate_chain().unwrap_err();
+        assert!(matches!(err, ValidationError::InsufficientDifficulty(1)));
+    }
+
+    #[test]
+    fn merkle_root_of_no_transactions_is_the_zero_hash() {
+        assert_eq!(merkle_root(&[]), "0".repeat(64));
+    }
+
+    #[test]
+    fn merkle_root_of_a_single_transaction_is_its_leaf_hash() {
+        let tx = Transaction { payload: "only".to_string() };
+        assert_eq!(merkle_root(&[tx]), sha256_hex(b"only"));
+    }
+
+    #[test]
+    fn merkle_root_is_order_sensitive() {
+        let a = Transaction { payload: "a".to_string() };
+        let b = Transaction { payload: "b".to_string() };
+        assert_ne!(merkle_root(&[a.clone(), b.clone()]), merkle_root(&[b, a]));
+    }
+
+    #[test]
+    fn merkle_root_duplicates_the_last_leaf_at_odd_levels() {
+        // Three transactions: the third leaf is duplicated to pair with
+        // itself, so the root should match hashing [h1, h2, h3, h3].
+        let txs: Vec<Transaction> = ["a", "b", "c"]
+            .iter()
+            .map(|p| Transaction { payload: p.to_string() })
+            .collect();
+
+        let leaves: Vec<String> = txs.iter().map(|tx| sha256_hex(tx.payload.as_bytes())).collect();
+        let top_left = sha256_hex(format!("{}{}", leaves[0], leaves[1]).as_bytes());
+        let top_right = sha256_hex(format!("{}{}", leaves[2], leaves[2]).as_bytes());
+        let expected = sha256_hex(format!("{}{}", top_left, top_right).as_bytes());
+
+        assert_eq!(merkle_root(&txs), expected);
+    }
 }